@@ -4,7 +4,7 @@ use std::time::Duration;
 #[tokio::main]
 async fn main() {
     println!("Starting connection to iRacing...");
-    let mut client = Client::connect().await;
+    let mut client = Client::connect(Duration::from_secs(5)).await;
     println!("Connected to memory interface!");
     loop {
         while !client.wait_for_data(Duration::from_millis(1000)) {
@@ -0,0 +1,259 @@
+//! Connects to a locally running iRacing session via its shared-memory
+//! telemetry interface.
+
+use crate::ntp::ClockSync;
+use crate::windows_util::SharedMemoryMap;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
+
+/// A single value read from the telemetry variable table.
+#[derive(Clone, Debug)]
+pub enum Value {
+    Bool(bool),
+    Int(i32),
+    Float(f32),
+    Double(f64),
+    BoolArray(Vec<bool>),
+    IntArray(Vec<i32>),
+    FloatArray(Vec<f32>),
+    DoubleArray(Vec<f64>),
+}
+
+/// One frame of telemetry variables, keyed by their iRacing variable name.
+#[derive(Clone, Debug)]
+pub struct Data {
+    pub vars: HashMap<String, Value>,
+    captured_at: Instant,
+    wall_clock_at: SystemTime,
+}
+
+/// Connection to a running iRacing session's shared-memory telemetry.
+pub struct Client {
+    memory_map: SharedMemoryMap,
+    last_session_info_update: i32,
+    cached_session_info_raw: Option<String>,
+    /// Offset applied to wall-clock timestamps on every [`Data`] this client
+    /// reads, if clock sync was requested. `None` means purely local time.
+    clock_sync: Option<ClockSync>,
+}
+
+impl Client {
+    /// Connects to iRacing, retrying every `retry_delay` until a session is found.
+    ///
+    /// Wall-clock timestamps on the returned [`Data`] are purely local; use
+    /// [`Self::connect_with_clock_sync`] to discipline them against an NTP
+    /// offset instead.
+    pub async fn connect(retry_delay: Duration) -> Self {
+        Self::connect_with_clock_sync(retry_delay, None).await
+    }
+
+    /// Connects to iRacing like [`Self::connect`], but disciplines every
+    /// returned [`Data`]'s
+    /// [`wall_clock_timestamp`](crate::Moment::wall_clock_timestamp) against
+    /// `clock_sync`, typically measured via
+    /// [`SimetryConnectionBuilder::resolve_clock_sync`](crate::SimetryConnectionBuilder::resolve_clock_sync).
+    pub async fn connect_with_clock_sync(
+        retry_delay: Duration,
+        clock_sync: Option<ClockSync>,
+    ) -> Self {
+        loop {
+            if let Some(memory_map) = SharedMemoryMap::open() {
+                return Self {
+                    memory_map,
+                    last_session_info_update: -1,
+                    cached_session_info_raw: None,
+                    clock_sync,
+                };
+            }
+            tokio::time::sleep(retry_delay).await;
+        }
+    }
+
+    /// Blocks (up to `timeout`) until a new telemetry frame is available.
+    pub fn wait_for_data(&mut self, timeout: Duration) -> bool {
+        self.memory_map.wait_for_data(timeout)
+    }
+
+    /// Returns whether iRacing is still running and the memory map is valid.
+    pub fn is_connected(&self) -> bool {
+        self.memory_map.is_valid()
+    }
+
+    /// Returns the most recently read telemetry frame, if any.
+    pub fn data(&self) -> Option<Data> {
+        let vars = self.memory_map.read_vars()?;
+        let now = SystemTime::now();
+        Some(Data {
+            vars,
+            captured_at: Instant::now(),
+            wall_clock_at: match &self.clock_sync {
+                Some(clock_sync) => clock_sync.apply(now),
+                None => now,
+            },
+        })
+    }
+
+    /// Returns the raw session info YAML exactly as iRacing provides it.
+    ///
+    /// The bytes are Windows-1252 encoded by iRacing, not UTF-8, so this
+    /// transcodes them before returning a Rust `String`. Windows-1252 rather
+    /// than strict ISO-8859-1/Latin-1: they agree outside 0x80-0x9F, and
+    /// iRacing's driver/track names are produced by Windows, which fills
+    /// that range with printable characters (curly quotes, em dash, etc.)
+    /// where Latin-1 has C1 control codes — decoding as Latin-1 would turn
+    /// those into mojibake instead.
+    pub fn session_info_raw(&mut self) -> Option<String> {
+        let update = self.memory_map.session_info_update();
+        if update != self.last_session_info_update || self.cached_session_info_raw.is_none() {
+            let raw_bytes = self.memory_map.session_info_bytes()?;
+            let (text, _encoding, _had_errors) = encoding_rs::WINDOWS_1252.decode(raw_bytes);
+            self.cached_session_info_raw = Some(text.into_owned());
+            self.last_session_info_update = update;
+        }
+        self.cached_session_info_raw.clone()
+    }
+
+    /// Returns the session info, parsed into typed Rust structures.
+    ///
+    /// This tolerates fields added by newer iRacing builds: any key not
+    /// present on [`SessionInfo`] (or its nested types) is silently ignored
+    /// rather than causing a parse error.
+    pub fn session_info(&mut self) -> Option<SessionInfo> {
+        let raw = self.session_info_raw()?;
+        match serde_yaml::from_str(&raw) {
+            Ok(session_info) => Some(session_info),
+            Err(err) => {
+                log::warn!("failed to parse iRacing session info YAML: {err}");
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl crate::Simetry for Client {
+    fn name(&self) -> &str {
+        "iRacing"
+    }
+
+    async fn next_moment(&mut self) -> Option<Box<dyn crate::Moment>> {
+        if !self.wait_for_data(Duration::from_millis(1000)) {
+            return None;
+        }
+        self.data().map(|data| Box::new(data) as Box<dyn crate::Moment>)
+    }
+}
+
+impl crate::Moment for Data {
+    fn timestamp(&self) -> Option<Instant> {
+        Some(self.captured_at)
+    }
+
+    fn wall_clock_timestamp(&self) -> Option<SystemTime> {
+        Some(self.wall_clock_at)
+    }
+}
+
+/// Top-level, typed view of iRacing's session info YAML blob.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SessionInfo {
+    #[serde(rename = "WeekendInfo", default)]
+    pub weekend_info: WeekendInfo,
+    #[serde(rename = "DriverInfo", default)]
+    pub driver_info: DriverInfo,
+    #[serde(rename = "SessionInfo", default)]
+    pub sessions: SessionsInfo,
+    #[serde(rename = "SplitTimeInfo", default)]
+    pub split_time_info: SplitTimeInfo,
+}
+
+/// Track and event metadata, e.g. track name, layout, and surface length.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct WeekendInfo {
+    #[serde(rename = "TrackName", default)]
+    pub track_name: String,
+    #[serde(rename = "TrackID", default)]
+    pub track_id: i32,
+    #[serde(rename = "TrackDisplayName", default)]
+    pub track_display_name: String,
+    #[serde(rename = "TrackConfigName", default)]
+    pub track_config_name: String,
+    #[serde(rename = "TrackLength", default)]
+    pub track_length: String,
+    #[serde(rename = "EventType", default)]
+    pub event_type: String,
+    #[serde(rename = "Category", default)]
+    pub category: String,
+    #[serde(rename = "SeriesID", default)]
+    pub series_id: i32,
+}
+
+/// The full driver/competitor list for the session; each entry carries its
+/// own `car_idx`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct DriverInfo {
+    #[serde(rename = "DriverCarIdx", default)]
+    pub driver_car_idx: i32,
+    #[serde(rename = "Drivers", default)]
+    pub drivers: Vec<DriverEntry>,
+}
+
+/// A single driver/competitor entry, as listed under `DriverInfo.Drivers`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct DriverEntry {
+    #[serde(rename = "CarIdx", default)]
+    pub car_idx: i32,
+    #[serde(rename = "UserName", default)]
+    pub user_name: String,
+    #[serde(rename = "TeamName", default)]
+    pub team_name: String,
+    #[serde(rename = "CarScreenName", default)]
+    pub car_screen_name: String,
+    #[serde(rename = "CarClassID", default)]
+    pub car_class_id: i32,
+    #[serde(rename = "CarNumber", default)]
+    pub car_number: String,
+    #[serde(rename = "IRating", default)]
+    pub irating: i32,
+}
+
+/// The list of sessions (practice/qualifying/race) making up the weekend.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SessionsInfo {
+    #[serde(rename = "Sessions", default)]
+    pub sessions: Vec<SessionEntry>,
+}
+
+/// One entry from `SessionInfo.Sessions`: a single session's number, type,
+/// name, lap count and track rubber state. Session flags are not included.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SessionEntry {
+    #[serde(rename = "SessionNum", default)]
+    pub session_num: i32,
+    #[serde(rename = "SessionType", default)]
+    pub session_type: String,
+    #[serde(rename = "SessionName", default)]
+    pub session_name: String,
+    #[serde(rename = "SessionLaps", default)]
+    pub session_laps: String,
+    #[serde(rename = "SessionTrackRubberState", default)]
+    pub session_track_rubber_state: String,
+}
+
+/// Track surface segmentation: sector boundaries as fractions of lap distance.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SplitTimeInfo {
+    #[serde(rename = "Sectors", default)]
+    pub sectors: Vec<SectorEntry>,
+}
+
+/// A single sector boundary, as listed under `SplitTimeInfo.Sectors`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SectorEntry {
+    #[serde(rename = "SectorNum", default)]
+    pub sector_num: i32,
+    #[serde(rename = "SectorStartPct", default)]
+    pub sector_start_pct: f64,
+}
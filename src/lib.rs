@@ -1,7 +1,13 @@
 pub use racing_flags::RacingFlags;
+use futures_util::stream::FuturesUnordered;
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
-use tokio::select;
+use std::collections::HashSet;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::time::{Duration, Instant, SystemTime};
 use uom::si::f64::{AngularVelocity, Velocity};
 
 pub mod assetto_corsa;
@@ -9,8 +15,12 @@ pub mod assetto_corsa_competizione;
 pub mod dirt_rally_2;
 pub mod generic_http;
 pub mod iracing;
+pub mod mqtt;
+pub mod ntp;
 mod racing_flags;
+pub mod replay;
 pub mod rfactor_2;
+pub mod server;
 pub mod truck_simulator;
 mod windows_util;
 
@@ -32,12 +42,88 @@ pub trait Simetry {
     async fn next_moment(&mut self) -> Option<Box<dyn Moment>>;
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// A user-supplied sim backend that can be raced alongside the built-ins via
+/// [`SimetryConnectionBuilder::register`].
+///
+/// This lets users integrate proprietary or niche sims (or e.g. the
+/// [`replay`] backends) without forking this crate.
+#[async_trait::async_trait]
+pub trait Connector: Send + Sync {
+    /// Connects to this backend, retrying every `retry_delay` until it succeeds.
+    async fn connect(&self, retry_delay: Duration) -> Box<dyn Simetry>;
+}
+
+impl fmt::Debug for dyn Connector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("dyn Connector")
+    }
+}
+
+type ConnectFuture = Pin<Box<dyn std::future::Future<Output = Box<dyn Simetry>> + Send>>;
+
+/// Identifies one of the built-in backends, for use with
+/// [`SimetryConnectionBuilder::enabled_backends`] to restrict which ones
+/// `connect()`/`connect_all()` even attempt.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum BackendKind {
+    IRacing,
+    AssettoCorsa,
+    AssettoCorsaCompetizione,
+    RFactor2,
+    DirtRally2,
+    GenericHttp,
+    TruckSimulator,
+}
+
+impl BackendKind {
+    const ALL: [BackendKind; 7] = [
+        BackendKind::IRacing,
+        BackendKind::AssettoCorsa,
+        BackendKind::AssettoCorsaCompetizione,
+        BackendKind::RFactor2,
+        BackendKind::DirtRally2,
+        BackendKind::GenericHttp,
+        BackendKind::TruckSimulator,
+    ];
+}
+
+// Note: no longer `Clone`/`Eq`/`PartialEq`, unlike the original builder.
+// `connectors` holds `Box<dyn Connector>` trait objects, which can't
+// generically implement those, and this wasn't worth a `dyn_clone`-style
+// workaround just to keep the derive. Builders are still consumed through
+// the usual `self -> Self` chain, so losing `Clone` shouldn't bite normal
+// usage; a caller that was cloning a populated builder to connect multiple
+// times will need to rebuild it (or switch to `connect_all`) instead.
+#[derive(Debug)]
 pub struct SimetryConnectionBuilder {
     pub generic_http_uri: String,
     pub truck_simulator_uri: String,
     pub dirt_rally_2_uri: String,
     pub retry_delay: Duration,
+    /// Bind address used by [`Self::connect_and_serve`] to expose the connected
+    /// sim's telemetry over HTTP. See [`server`].
+    pub telemetry_server_addr: SocketAddr,
+    /// If set, `connect()` feeds recorded data from this path via
+    /// [`replay::ReplayClient`] instead of racing the built-in sim backends.
+    /// If the recording fails to load, the error is logged and the backends
+    /// are raced as usual.
+    pub replay_path: Option<PathBuf>,
+    /// Speed multiplier applied to a replay's original inter-frame timing.
+    /// Only used when `replay_path` is set. `0.0` replays with no delay.
+    pub replay_speed: f64,
+    /// Custom backends registered via [`Self::register`], raced alongside the
+    /// built-in ones in [`Self::connect`].
+    connectors: Vec<Box<dyn Connector>>,
+    /// If set, discipline [`Moment::wall_clock_timestamp`] readings against
+    /// an NTP server so they share a drift-corrected timeline across
+    /// multiple sources. Timestamps stay purely local/monotonic otherwise.
+    pub clock_sync: Option<ntp::ClockSyncOptions>,
+    /// Restricts which built-in backends `connect()`/`connect_all()` attempt.
+    /// Defaults to all of them; registered [`Connector`]s are unaffected.
+    pub enabled_backends: HashSet<BackendKind>,
+    /// Deadline after which [`Self::connect_all`] stops waiting on backends
+    /// that haven't connected yet.
+    pub connect_all_deadline: Duration,
 }
 
 impl Default for SimetryConnectionBuilder {
@@ -47,34 +133,190 @@ impl Default for SimetryConnectionBuilder {
             truck_simulator_uri: truck_simulator::DEFAULT_URI.to_string(),
             dirt_rally_2_uri: dirt_rally_2::Client::DEFAULT_URI.to_string(),
             retry_delay: Duration::from_secs(5),
+            telemetry_server_addr: server::DEFAULT_BIND_ADDR,
+            replay_path: None,
+            replay_speed: 1.0,
+            connectors: Vec::new(),
+            clock_sync: None,
+            enabled_backends: BackendKind::ALL.into_iter().collect(),
+            connect_all_deadline: Duration::from_secs(10),
         }
     }
 }
 
 impl SimetryConnectionBuilder {
     pub async fn connect(self) -> Box<dyn Simetry> {
+        if let Some(replay_path) = &self.replay_path {
+            match replay::ReplayClient::load(replay_path, self.replay_speed) {
+                Ok(client) => return Box::new(client),
+                Err(err) => log::error!(
+                    "failed to load replay from {}, falling back to live sims: {err}",
+                    replay_path.display()
+                ),
+            }
+        }
+
         let retry_delay = self.retry_delay;
-        let iracing_future = iracing::Client::connect(retry_delay);
-        let assetto_corsa_future = assetto_corsa::Client::connect(retry_delay);
-        let assetto_corsa_competizione_future =
-            assetto_corsa_competizione::Client::connect(retry_delay);
-        let rfactor_2_future = rfactor_2::Client::connect();
-        let dirt_rally_2_future =
-            dirt_rally_2::Client::connect(&self.dirt_rally_2_uri, retry_delay);
-        let generic_http_future =
-            generic_http::GenericHttpClient::connect(&self.generic_http_uri, retry_delay);
-        let truck_simulator_future =
-            truck_simulator::TruckSimulatorClient::connect(&self.truck_simulator_uri, retry_delay);
-
-        select! {
-            x = iracing_future => Box::new(x),
-            x = assetto_corsa_future => Box::new(x),
-            x = assetto_corsa_competizione_future => Box::new(x),
-            x = rfactor_2_future => Box::new(x),
-            x = dirt_rally_2_future => Box::new(x),
-            x = generic_http_future => Box::new(x),
-            x = truck_simulator_future => Box::new(x),
+        let clock_sync = self.resolve_clock_sync().await;
+        let mut connects: FuturesUnordered<ConnectFuture> =
+            self.built_in_connect_futures(clock_sync);
+
+        for connector in self.connectors {
+            connects.push(Box::pin(async move { connector.connect(retry_delay).await }));
+        }
+
+        if connects.is_empty() {
+            // `enabled_backends` was cleared and no `Connector` was registered:
+            // there is nothing to race. Rather than panic on this reachable
+            // misconfiguration, never resolve, consistent with every backend
+            // here retrying forever instead of failing.
+            log::error!(
+                "SimetryConnectionBuilder::connect() has no enabled backends and no \
+                 registered connectors; this call will never resolve"
+            );
+            return std::future::pending().await;
         }
+
+        connects
+            .next()
+            .await
+            .expect("connects was checked to be non-empty above")
+    }
+
+    /// Returns the enabled built-in backends' connect futures, raced by
+    /// [`Self::connect`] and awaited by [`Self::connect_all`].
+    fn built_in_connect_futures(
+        &self,
+        clock_sync: Option<ntp::ClockSync>,
+    ) -> FuturesUnordered<ConnectFuture> {
+        let retry_delay = self.retry_delay;
+        let dirt_rally_2_uri = self.dirt_rally_2_uri.clone();
+        let generic_http_uri = self.generic_http_uri.clone();
+        let truck_simulator_uri = self.truck_simulator_uri.clone();
+        let enabled = &self.enabled_backends;
+
+        let futures: FuturesUnordered<ConnectFuture> = FuturesUnordered::new();
+        if enabled.contains(&BackendKind::IRacing) {
+            futures.push(Box::pin(async move {
+                Box::new(iracing::Client::connect_with_clock_sync(retry_delay, clock_sync).await)
+                    as Box<dyn Simetry>
+            }));
+        }
+        if enabled.contains(&BackendKind::AssettoCorsa) {
+            futures.push(Box::pin(async move {
+                Box::new(assetto_corsa::Client::connect(retry_delay).await) as Box<dyn Simetry>
+            }));
+        }
+        if enabled.contains(&BackendKind::AssettoCorsaCompetizione) {
+            futures.push(Box::pin(async move {
+                Box::new(assetto_corsa_competizione::Client::connect(retry_delay).await)
+                    as Box<dyn Simetry>
+            }));
+        }
+        if enabled.contains(&BackendKind::RFactor2) {
+            futures.push(Box::pin(async move {
+                Box::new(rfactor_2::Client::connect().await) as Box<dyn Simetry>
+            }));
+        }
+        if enabled.contains(&BackendKind::DirtRally2) {
+            futures.push(Box::pin(async move {
+                Box::new(dirt_rally_2::Client::connect(&dirt_rally_2_uri, retry_delay).await)
+                    as Box<dyn Simetry>
+            }));
+        }
+        if enabled.contains(&BackendKind::GenericHttp) {
+            futures.push(Box::pin(async move {
+                Box::new(
+                    generic_http::GenericHttpClient::connect(&generic_http_uri, retry_delay).await,
+                ) as Box<dyn Simetry>
+            }));
+        }
+        if enabled.contains(&BackendKind::TruckSimulator) {
+            futures.push(Box::pin(async move {
+                Box::new(
+                    truck_simulator::TruckSimulatorClient::connect(
+                        &truck_simulator_uri,
+                        retry_delay,
+                    )
+                    .await,
+                ) as Box<dyn Simetry>
+            }));
+        }
+        futures
+    }
+
+    /// Awaits every enabled backend (built-in and registered) concurrently
+    /// and returns every one that connected within [`Self::connect_all_deadline`],
+    /// instead of discarding all but the first to respond like [`Self::connect`].
+    ///
+    /// Useful for running several sims at once (e.g. iRacing alongside a
+    /// truck sim) or merging multiple data feeds; route each result by its
+    /// [`Simetry::name`].
+    pub async fn connect_all(self) -> Vec<Box<dyn Simetry>> {
+        let retry_delay = self.retry_delay;
+        let deadline = self.connect_all_deadline;
+        let clock_sync = self.resolve_clock_sync().await;
+        let mut connects: Vec<ConnectFuture> = self
+            .built_in_connect_futures(clock_sync)
+            .into_iter()
+            .collect();
+
+        for connector in self.connectors {
+            connects.push(Box::pin(async move { connector.connect(retry_delay).await }));
+        }
+
+        futures_util::future::join_all(connects.into_iter().map(|connect| async move {
+            tokio::time::timeout(deadline, connect).await.ok()
+        }))
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Registers a custom backend to be raced alongside the built-in ones.
+    ///
+    /// This is the extension point for proprietary or niche sims: implement
+    /// [`Connector`] and register it instead of forking this crate.
+    pub fn register(mut self, connector: impl Connector + 'static) -> Self {
+        self.connectors.push(Box::new(connector));
+        self
+    }
+
+    /// Measures the configured [`Self::clock_sync`] offset, if any.
+    ///
+    /// [`Self::connect`] and [`Self::connect_all`] call this once and pass
+    /// the resulting [`ntp::ClockSync`] (if any) to the iRacing backend,
+    /// which applies it to every [`Moment::wall_clock_timestamp`] it reports.
+    ///
+    /// [`ntp::ClockSync::measure`] does blocking socket I/O, so this runs it
+    /// on [`tokio::task::spawn_blocking`] rather than parking the calling
+    /// worker (and, on a current-thread runtime, the whole connect race) for
+    /// the up-to-`sample_count * timeout` it can take.
+    pub async fn resolve_clock_sync(&self) -> Option<ntp::ClockSync> {
+        let opts = self.clock_sync.clone()?;
+        let server_addr = opts.server_addr.clone();
+        match tokio::task::spawn_blocking(move || ntp::ClockSync::measure(&opts)).await {
+            Ok(Ok(sync)) => Some(sync),
+            Ok(Err(err)) => {
+                log::error!("failed to synchronize clock against {server_addr}: {err}");
+                None
+            }
+            Err(join_err) => {
+                log::error!("clock sync task panicked: {join_err}");
+                None
+            }
+        }
+    }
+
+    /// Connects to a sim, then serves its telemetry as JSON over HTTP on
+    /// [`Self::telemetry_server_addr`] for as long as the connection lasts.
+    ///
+    /// See [`server::serve`] for the exposed endpoints.
+    pub async fn connect_and_serve(self) -> tokio::task::JoinHandle<()> {
+        let bind_addr = self.telemetry_server_addr;
+        let sim = self.connect().await;
+        tokio::spawn(server::serve(sim, bind_addr))
     }
 }
 
@@ -137,6 +379,25 @@ pub trait Moment {
     fn starter_on(&self) -> bool {
         false
     }
+
+    /// Monotonic timestamp of when this moment was captured.
+    ///
+    /// Lets consumers compute accurate frame deltas and detect dropped
+    /// frames. `None` if the backend does not track timestamps.
+    fn timestamp(&self) -> Option<Instant> {
+        None
+    }
+
+    /// Wall-clock timestamp of when this moment was captured.
+    ///
+    /// When [`SimetryConnectionBuilder::clock_sync`] is enabled, backends
+    /// that support it apply the resulting offset here so moments from
+    /// multiple sources land on the same drift-corrected timeline. Purely
+    /// local wall-clock time otherwise. `None` if the backend does not
+    /// track timestamps.
+    fn wall_clock_timestamp(&self) -> Option<SystemTime> {
+        None
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -0,0 +1,119 @@
+//! An optional MQTT sink that publishes each [`Moment`] reading to a broker,
+//! so multiple dashboards (or a home-automation setup) can subscribe to live
+//! race data without each one opening its own sim connection.
+//!
+//! Readings are published as JSON under `simetry/<sim_name>/telemetry`,
+//! `simetry/<sim_name>/flags`, and `simetry/<sim_name>/shift_point`.
+
+use crate::{Moment, Simetry};
+use rumqttc::{AsyncClient, ClientError, MqttOptions, QoS};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// Options controlling how [`publish_loop`] connects to the broker and how
+/// often it publishes.
+#[derive(Clone, Debug)]
+pub struct MqttPublishOptions {
+    /// MQTT client identifier presented to the broker.
+    pub client_id: String,
+    /// Quality of service used for every published message.
+    pub qos: QoS,
+    /// Minimum time between publishes; readings in between are dropped.
+    pub publish_interval: Duration,
+    /// Keep-alive ping interval for the underlying MQTT connection.
+    pub keep_alive: Duration,
+    /// Maximum number of unacknowledged in-flight publishes.
+    pub max_inflight: u16,
+}
+
+impl Default for MqttPublishOptions {
+    fn default() -> Self {
+        Self {
+            client_id: "simetry".to_string(),
+            qos: QoS::AtMostOnce,
+            publish_interval: Duration::from_millis(100),
+            keep_alive: Duration::from_secs(5),
+            max_inflight: 10,
+        }
+    }
+}
+
+/// Connects to the MQTT broker at `broker_host:broker_port` and publishes
+/// `next_moment()` readings from `sim`, at most one per
+/// `opts.publish_interval`, until the sim connection ends or a publish fails.
+pub async fn publish_loop(
+    mut sim: Box<dyn Simetry>,
+    broker_host: &str,
+    broker_port: u16,
+    opts: MqttPublishOptions,
+) -> Result<(), ClientError> {
+    let sim_name = sim.name().to_string();
+
+    let mut mqtt_options = MqttOptions::new(opts.client_id.clone(), broker_host, broker_port);
+    mqtt_options.set_keep_alive(opts.keep_alive);
+    mqtt_options.set_inflight(opts.max_inflight);
+
+    let (client, mut event_loop) = AsyncClient::new(mqtt_options, opts.max_inflight as usize);
+    let event_loop_task = tokio::spawn(async move {
+        loop {
+            if let Err(err) = event_loop.poll().await {
+                log::error!("simetry::mqtt connection to broker lost: {err}");
+                break;
+            }
+        }
+    });
+
+    let mut last_publish = Instant::now() - opts.publish_interval;
+    let result = loop {
+        match sim.next_moment().await {
+            Some(moment) => {
+                if last_publish.elapsed() < opts.publish_interval {
+                    continue;
+                }
+                last_publish = Instant::now();
+                if let Err(err) = publish_moment(&client, &sim_name, moment.as_ref(), opts.qos).await
+                {
+                    break Err(err);
+                }
+            }
+            None => break Ok(()),
+        }
+    };
+
+    // The sim connection ended (or a publish failed): stop polling the
+    // broker connection rather than leaving it running on a dead sim.
+    event_loop_task.abort();
+    result
+}
+
+async fn publish_moment(
+    client: &AsyncClient,
+    sim_name: &str,
+    moment: &dyn Moment,
+    qos: QoS,
+) -> Result<(), ClientError> {
+    if let Some(telemetry) = moment.basic_telemetry() {
+        publish_json(client, &format!("simetry/{sim_name}/telemetry"), &telemetry, qos).await?;
+    }
+    publish_json(client, &format!("simetry/{sim_name}/flags"), &moment.flags(), qos).await?;
+    if let Some(shift_point) = moment.shift_point() {
+        publish_json(
+            client,
+            &format!("simetry/{sim_name}/shift_point"),
+            &shift_point.value,
+            qos,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+async fn publish_json<T: Serialize>(
+    client: &AsyncClient,
+    topic: &str,
+    value: &T,
+    qos: QoS,
+) -> Result<(), ClientError> {
+    let payload = serde_json::to_vec(value).expect("telemetry types are always serializable");
+    client.publish(topic, qos, false, payload).await
+}
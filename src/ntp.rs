@@ -0,0 +1,113 @@
+//! NTP-based clock synchronization.
+//!
+//! Used by [`crate::SimetryConnectionBuilder`]'s clock-sync mode to discipline
+//! [`Moment`](crate::Moment) wall-clock timestamps against an NTP server, so
+//! readings from multiple sources (e.g. sim telemetry paired with externally
+//! captured data) land on one shared, drift-corrected timeline.
+
+use std::net::UdpSocket;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const NTP_UNIX_EPOCH_OFFSET_SECONDS: i128 = 2_208_988_800;
+
+/// Options controlling how [`ClockSync::measure`] samples an NTP server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClockSyncOptions {
+    /// `host:port` of the NTP server to sample, e.g. `"pool.ntp.org:123"`.
+    pub server_addr: String,
+    /// Number of round trips to sample; the lowest-delay sample is kept.
+    pub sample_count: u32,
+    /// Timeout for each individual round trip.
+    pub timeout: Duration,
+}
+
+impl Default for ClockSyncOptions {
+    fn default() -> Self {
+        Self {
+            server_addr: "pool.ntp.org:123".to_string(),
+            sample_count: 8,
+            timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+/// The outcome of synchronizing against an NTP server: an offset to apply to
+/// local wall-clock time to land on the server's timeline.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClockSync {
+    offset_nanos: i128,
+    round_trip_delay: Duration,
+}
+
+impl ClockSync {
+    /// Samples `opts.sample_count` NTP round trips against `opts.server_addr`
+    /// and keeps the lowest-delay sample, per the usual NTP offset formula:
+    /// `offset = ((t1-t0)+(t2-t3))/2`, `round_trip_delay = (t3-t0)-(t2-t1)`.
+    pub fn measure(opts: &ClockSyncOptions) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.set_read_timeout(Some(opts.timeout))?;
+        socket.connect(&opts.server_addr)?;
+
+        let mut best: Option<Self> = None;
+        for _ in 0..opts.sample_count.max(1) {
+            if let Ok(sample) = Self::sample_once(&socket) {
+                if best.map_or(true, |b| sample.round_trip_delay < b.round_trip_delay) {
+                    best = Some(sample);
+                }
+            }
+        }
+        best.ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::TimedOut, "no NTP round trip succeeded")
+        })
+    }
+
+    fn sample_once(socket: &UdpSocket) -> std::io::Result<Self> {
+        let mut request = [0u8; 48];
+        request[0] = 0b00_100_011; // LI = 0 (no warning), VN = 4, Mode = 3 (client)
+
+        let t0 = unix_nanos(SystemTime::now());
+        socket.send(&request)?;
+        let mut response = [0u8; 48];
+        socket.recv(&mut response)?;
+        let t3 = unix_nanos(SystemTime::now());
+
+        let t1 = read_ntp_timestamp(&response[32..40]);
+        let t2 = read_ntp_timestamp(&response[40..48]);
+
+        let offset_nanos = ((t1 - t0) + (t2 - t3)) / 2;
+        let round_trip_nanos = (t3 - t0) - (t2 - t1);
+
+        Ok(Self {
+            offset_nanos,
+            round_trip_delay: Duration::from_nanos(round_trip_nanos.unsigned_abs() as u64),
+        })
+    }
+
+    /// Round-trip delay of the sample this [`ClockSync`] was built from.
+    pub fn round_trip_delay(&self) -> Duration {
+        self.round_trip_delay
+    }
+
+    /// Applies this sync's offset to a local wall-clock timestamp.
+    pub fn apply(&self, local: SystemTime) -> SystemTime {
+        if self.offset_nanos >= 0 {
+            local + Duration::from_nanos(self.offset_nanos as u64)
+        } else {
+            local - Duration::from_nanos((-self.offset_nanos) as u64)
+        }
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> i128 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as i128
+}
+
+/// Reads a 64-bit NTP timestamp (32-bit seconds since 1900 + 32-bit fraction)
+/// as nanoseconds since the Unix epoch.
+fn read_ntp_timestamp(bytes: &[u8]) -> i128 {
+    let seconds = u32::from_be_bytes(bytes[0..4].try_into().unwrap()) as i128
+        - NTP_UNIX_EPOCH_OFFSET_SECONDS;
+    let fraction = u32::from_be_bytes(bytes[4..8].try_into().unwrap()) as i128;
+    let nanos = (fraction * 1_000_000_000) >> 32;
+    seconds * 1_000_000_000 + nanos
+}
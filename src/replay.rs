@@ -0,0 +1,262 @@
+//! Record a stream of [`Moment`]s to a file and replay it later through a
+//! [`Simetry`] implementation, plus an in-memory [`MockClient`] for
+//! deterministic tests that need no I/O at all.
+//!
+//! Recordings are stored as newline-delimited JSON: one [`RecordedMoment`]
+//! per line, in the order they were observed.
+
+use crate::{BasicTelemetry, Moment, RacingFlags, Simetry};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+use uom::si::angular_velocity::radian_per_second;
+use uom::si::f64::AngularVelocity;
+
+/// A single recorded [`Moment`], serializable as one line of newline-delimited JSON.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RecordedMoment {
+    /// How long after the previous recorded moment this one was observed.
+    pub since_previous: Duration,
+    /// The original moment's [`Moment::wall_clock_timestamp`], if it had one.
+    ///
+    /// [`Moment::timestamp`] is not preserved: an [`Instant`] is only
+    /// meaningful within the process that produced it, so it can't be
+    /// serialized or faithfully replayed across a run.
+    pub wall_clock_at: Option<SystemTime>,
+    pub basic_telemetry: Option<BasicTelemetry>,
+    pub shift_point: Option<f64>,
+    pub flags: RacingFlags,
+    pub vehicle_unique_id: Option<String>,
+    pub vehicle_left: bool,
+    pub vehicle_right: bool,
+    pub ignition_on: bool,
+    pub starter_on: bool,
+}
+
+impl RecordedMoment {
+    fn capture(moment: &dyn Moment, since_previous: Duration) -> Self {
+        Self {
+            since_previous,
+            wall_clock_at: moment.wall_clock_timestamp(),
+            basic_telemetry: moment.basic_telemetry(),
+            shift_point: moment.shift_point().map(|value| value.value),
+            flags: moment.flags(),
+            vehicle_unique_id: moment.vehicle_unique_id(),
+            vehicle_left: moment.vehicle_left(),
+            vehicle_right: moment.vehicle_right(),
+            ignition_on: moment.ignition_on(),
+            starter_on: moment.starter_on(),
+        }
+    }
+}
+
+impl Moment for RecordedMoment {
+    fn vehicle_left(&self) -> bool {
+        self.vehicle_left
+    }
+
+    fn vehicle_right(&self) -> bool {
+        self.vehicle_right
+    }
+
+    fn basic_telemetry(&self) -> Option<BasicTelemetry> {
+        self.basic_telemetry.clone()
+    }
+
+    fn shift_point(&self) -> Option<AngularVelocity> {
+        self.shift_point
+            .map(AngularVelocity::new::<radian_per_second>)
+    }
+
+    fn flags(&self) -> RacingFlags {
+        self.flags.clone()
+    }
+
+    fn vehicle_unique_id(&self) -> Option<String> {
+        self.vehicle_unique_id.clone()
+    }
+
+    fn ignition_on(&self) -> bool {
+        self.ignition_on
+    }
+
+    fn starter_on(&self) -> bool {
+        self.starter_on
+    }
+
+    fn wall_clock_timestamp(&self) -> Option<SystemTime> {
+        self.wall_clock_at
+    }
+}
+
+/// Records every `next_moment()` from `sim` to `path` as newline-delimited
+/// JSON until the connection ends.
+pub async fn record(mut sim: Box<dyn Simetry>, path: impl AsRef<Path>) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    let mut last = Instant::now();
+    while let Some(moment) = sim.next_moment().await {
+        let now = Instant::now();
+        let recorded = RecordedMoment::capture(moment.as_ref(), now.duration_since(last));
+        last = now;
+        let line = serde_json::to_string(&recorded).expect("RecordedMoment is always serializable");
+        writeln!(file, "{line}")?;
+    }
+    Ok(())
+}
+
+/// Replays a recording made by [`record`] as a [`Simetry`] backend, honoring
+/// the original inter-frame timing (scaled by `speed`, e.g. `2.0` for double speed).
+pub struct ReplayClient {
+    moments: std::vec::IntoIter<RecordedMoment>,
+    speed: f64,
+}
+
+impl ReplayClient {
+    /// Loads a recording from `path`. `speed` scales the original inter-frame
+    /// delays; `0.0` replays every moment back to back with no delay.
+    pub fn load(path: impl AsRef<Path>, speed: f64) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let reader = std::io::BufReader::new(file);
+        let mut moments = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let moment: RecordedMoment = serde_json::from_str(&line)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+            moments.push(moment);
+        }
+        Ok(Self {
+            moments: moments.into_iter(),
+            speed,
+        })
+    }
+}
+
+#[async_trait]
+impl Simetry for ReplayClient {
+    fn name(&self) -> &str {
+        "Replay"
+    }
+
+    async fn next_moment(&mut self) -> Option<Box<dyn Moment>> {
+        let moment = self.moments.next()?;
+        if self.speed > 0.0 {
+            tokio::time::sleep(moment.since_previous.div_f64(self.speed)).await;
+        }
+        Some(Box::new(moment))
+    }
+}
+
+/// An in-memory mock [`Simetry`] that yields a fixed, scripted sequence of
+/// moments with no I/O, so downstream crates can write deterministic unit
+/// tests of overlay/strategy logic without a running game.
+pub struct MockClient {
+    name: String,
+    moments: std::vec::IntoIter<RecordedMoment>,
+}
+
+impl MockClient {
+    pub fn new(name: impl Into<String>, moments: Vec<RecordedMoment>) -> Self {
+        Self {
+            name: name.into(),
+            moments: moments.into_iter(),
+        }
+    }
+}
+
+#[async_trait]
+impl Simetry for MockClient {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn next_moment(&mut self) -> Option<Box<dyn Moment>> {
+        self.moments.next().map(|m| Box::new(m) as Box<dyn Moment>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moment(ignition_on: bool, since_previous_ms: u64) -> RecordedMoment {
+        RecordedMoment {
+            since_previous: Duration::from_millis(since_previous_ms),
+            ignition_on,
+            ..Default::default()
+        }
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("simetry-replay-test-{}-{name}.ndjson", std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn mock_client_yields_scripted_sequence_in_order() {
+        let mut mock = MockClient::new("mock", vec![moment(true, 0), moment(false, 16)]);
+
+        assert_eq!(mock.name(), "mock");
+        assert!(mock.next_moment().await.expect("first moment").ignition_on());
+        assert!(!mock.next_moment().await.expect("second moment").ignition_on());
+        assert!(mock.next_moment().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn replay_client_honors_order_and_speed() {
+        let path = temp_path("order-and-speed");
+        {
+            let mut file = std::fs::File::create(&path).unwrap();
+            for m in [moment(true, 0), moment(false, 100)] {
+                writeln!(file, "{}", serde_json::to_string(&m).unwrap()).unwrap();
+            }
+        }
+
+        let mut replay = ReplayClient::load(&path, 2.0).expect("replay should load");
+        assert_eq!(replay.name(), "Replay");
+
+        assert!(replay
+            .next_moment()
+            .await
+            .expect("first moment")
+            .ignition_on());
+
+        let before = tokio::time::Instant::now();
+        let second = replay.next_moment().await.expect("second moment");
+        assert!(!second.ignition_on());
+        // since_previous (100ms) scaled by speed 2.0 => 50ms.
+        assert_eq!(before.elapsed(), Duration::from_millis(50));
+
+        assert!(replay.next_moment().await.is_none());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn record_then_replay_round_trips_moment_data() {
+        let path = temp_path("record-round-trip");
+
+        let sim: Box<dyn Simetry> = Box::new(MockClient::new("mock", vec![
+            moment(true, 0),
+            moment(false, 0),
+        ]));
+        record(sim, &path).await.expect("record should succeed");
+
+        let mut replay = ReplayClient::load(&path, 0.0).expect("replay should load");
+        assert!(replay
+            .next_moment()
+            .await
+            .expect("first moment")
+            .ignition_on());
+        assert!(!replay
+            .next_moment()
+            .await
+            .expect("second moment")
+            .ignition_on());
+        assert!(replay.next_moment().await.is_none());
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,92 @@
+//! A small built-in HTTP server that exposes the unified [`Moment`] data of
+//! whichever [`Simetry`] backend is connected as plain JSON.
+//!
+//! This lets overlays, stream widgets, and tools written in other languages
+//! consume any supported sim through one stable endpoint, without caring
+//! which backend actually won the connection race in [`connect`](crate::connect).
+
+use crate::{BasicTelemetry, Moment, RacingFlags, Simetry};
+use axum::extract::State;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Serialize;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Default bind address for [`serve`]: `127.0.0.1:8080`.
+pub const DEFAULT_BIND_ADDR: SocketAddr =
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
+
+/// Snapshot of a [`Moment`], serialized as the body of `GET /telemetry`.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct TelemetrySnapshot {
+    pub basic_telemetry: Option<BasicTelemetry>,
+    pub shift_point: Option<f64>,
+    pub flags: RacingFlags,
+    pub vehicle_unique_id: Option<String>,
+    pub vehicle_left: bool,
+    pub vehicle_right: bool,
+    pub ignition_on: bool,
+    pub starter_on: bool,
+}
+
+impl TelemetrySnapshot {
+    fn from_moment(moment: &dyn Moment) -> Self {
+        Self {
+            basic_telemetry: moment.basic_telemetry(),
+            shift_point: moment.shift_point().map(|value| value.value),
+            flags: moment.flags(),
+            vehicle_unique_id: moment.vehicle_unique_id(),
+            vehicle_left: moment.vehicle_left(),
+            vehicle_right: moment.vehicle_right(),
+            ignition_on: moment.ignition_on(),
+            starter_on: moment.starter_on(),
+        }
+    }
+}
+
+type SharedSnapshot = Arc<RwLock<TelemetrySnapshot>>;
+
+/// Polls `sim` for new [`Moment`]s until the connection ends, serving the
+/// latest one as JSON over HTTP on `bind_addr`.
+///
+/// - `GET /telemetry` returns the full [`TelemetrySnapshot`].
+/// - `GET /flags` returns just the current [`RacingFlags`].
+pub async fn serve(mut sim: Box<dyn Simetry>, bind_addr: SocketAddr) {
+    let snapshot: SharedSnapshot = Arc::new(RwLock::new(TelemetrySnapshot::default()));
+
+    let app = Router::new()
+        .route("/telemetry", get(get_telemetry))
+        .route("/flags", get(get_flags))
+        .with_state(snapshot.clone());
+
+    let listener = match tokio::net::TcpListener::bind(bind_addr).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            log::error!("simetry::server failed to bind {bind_addr}: {err}");
+            return;
+        }
+    };
+    let server_task = tokio::spawn(async move {
+        if let Err(err) = axum::serve(listener, app).await {
+            log::error!("simetry::server stopped serving {bind_addr}: {err}");
+        }
+    });
+
+    while let Some(moment) = sim.next_moment().await {
+        *snapshot.write().await = TelemetrySnapshot::from_moment(moment.as_ref());
+    }
+
+    // The sim connection ended: stop serving rather than leaving the listener
+    // task running forever on a now-stale snapshot.
+    server_task.abort();
+}
+
+async fn get_telemetry(State(snapshot): State<SharedSnapshot>) -> Json<TelemetrySnapshot> {
+    Json(snapshot.read().await.clone())
+}
+
+async fn get_flags(State(snapshot): State<SharedSnapshot>) -> Json<RacingFlags> {
+    Json(snapshot.read().await.flags.clone())
+}